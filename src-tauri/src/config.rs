@@ -20,3 +20,65 @@ pub fn metrics_port() -> u16 {
         .and_then(|value| value.parse::<u16>().ok())
         .unwrap_or(9100)
 }
+
+// 获取指标服务器绑定地址
+pub fn metrics_bind() -> String {
+    env::var("MAALOGS_METRICS_BIND").unwrap_or_else(|_| "127.0.0.1".to_string())
+}
+
+// 获取保护 /metrics 端点的 Bearer token，未设置时端点不做鉴权
+pub fn metrics_token() -> Option<String> {
+    env::var("MAALOGS_METRICS_TOKEN").ok()
+}
+
+// 获取指标推送到前端的间隔（毫秒），0 表示关闭推送
+pub fn metrics_push_interval_ms() -> u64 {
+    env::var("MAALOGS_METRICS_PUSH_INTERVAL_MS")
+        .ok()
+        .and_then(|value| value.parse::<u64>().ok())
+        .unwrap_or(2000)
+}
+
+// 指标暴露模式：拉取（pull，默认）、推送（push）或两者都启用（both）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricsMode {
+    Pull,
+    Push,
+    Both,
+}
+
+impl MetricsMode {
+    pub fn pull_enabled(self) -> bool {
+        matches!(self, MetricsMode::Pull | MetricsMode::Both)
+    }
+
+    pub fn push_enabled(self) -> bool {
+        matches!(self, MetricsMode::Push | MetricsMode::Both)
+    }
+}
+
+// 获取指标暴露模式，默认 pull
+pub fn metrics_mode() -> MetricsMode {
+    match env::var("MAALOGS_METRICS_MODE")
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+        .as_str()
+    {
+        "push" => MetricsMode::Push,
+        "both" => MetricsMode::Both,
+        _ => MetricsMode::Pull,
+    }
+}
+
+// 获取 Pushgateway 地址，未设置时返回 None
+pub fn metrics_pushgateway_url() -> Option<String> {
+    env::var("MAALOGS_METRICS_PUSHGATEWAY_URL").ok()
+}
+
+// 获取推送到 Pushgateway 的间隔（毫秒）
+pub fn metrics_pushgateway_interval_ms() -> u64 {
+    env::var("MAALOGS_METRICS_PUSHGATEWAY_INTERVAL_MS")
+        .ok()
+        .and_then(|value| value.parse::<u64>().ok())
+        .unwrap_or(15_000)
+}
@@ -5,44 +5,53 @@
 // - 应用程序生命周期管理
 
 mod config;
+mod logtail;
 mod metrics;
-
-use std::time::Instant;
+mod metrics_push;
 
 // 问候命令 - 接收一个名字并返回问候语
 #[tauri::command]
 fn greet(name: &str) -> String {
-    let start = Instant::now();
-    let result = format!("Hello, {}! You've been greeted from Rust!", name);
-    metrics::observe_command("greet", "success", start.elapsed().as_secs_f64());
-    result
+    metrics::observe_infallible("greet", || {
+        format!("Hello, {}! You've been greeted from Rust!", name)
+    })
 }
 
 // 打开开发者工具命令
 #[tauri::command]
 fn open_devtools(window: tauri::WebviewWindow) -> Result<(), String> {
-    let start = Instant::now();
-    window.open_devtools();
-    metrics::observe_command("open_devtools", "success", start.elapsed().as_secs_f64());
-    Ok(())
+    metrics::observe("open_devtools", || {
+        window.open_devtools();
+        Ok(())
+    })
 }
 
 // 获取应用程序版本号命令
 #[tauri::command]
 fn get_app_version(app: tauri::AppHandle) -> String {
-    let start = Instant::now();
-    let result = app.package_info().version.to_string();
-    metrics::observe_command("get_app_version", "success", start.elapsed().as_secs_f64());
-    result
+    metrics::observe_infallible("get_app_version", || app.package_info().version.to_string())
 }
 
 // 应用程序运行入口 - 配置并启动 Tauri 应用程序
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run(context: tauri::Context<tauri::Wry>) {
     tauri::Builder::default()
-        .setup(|_app| {
+        .setup(|app| {
             if config::metrics_enabled() {
-                metrics::start_metrics_server(config::metrics_port());
+                let mode = config::metrics_mode();
+                if mode.pull_enabled() {
+                    metrics::start_metrics_server(
+                        config::metrics_bind(),
+                        config::metrics_port(),
+                        config::metrics_token(),
+                    );
+                }
+                if mode.push_enabled() {
+                    if let Some(url) = config::metrics_pushgateway_url() {
+                        metrics::start_pushgateway(url, config::metrics_pushgateway_interval_ms());
+                    }
+                }
+                metrics_push::start(app.handle().clone(), config::metrics_push_interval_ms());
             }
             Ok(())
         })
@@ -51,7 +60,13 @@ pub fn run(context: tauri::Context<tauri::Wry>) {
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_store::Builder::new().build())
-        .invoke_handler(tauri::generate_handler![greet, open_devtools, get_app_version])
+        .invoke_handler(tauri::generate_handler![
+            greet,
+            open_devtools,
+            get_app_version,
+            logtail::tail_start,
+            logtail::tail_stop
+        ])
         .run(context)
         .expect("error while running tauri application");
 }
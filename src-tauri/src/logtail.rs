@@ -0,0 +1,192 @@
+// 日志尾随（tail -f）模块
+// 本模块提供对本地日志文件的流式读取能力：前端通过 `tail_start` 订阅一个
+// 文件，后台线程持续读取新增的完整行并通过 Tauri 事件批量推送，
+// 通过 `tail_stop` 取消订阅。
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use once_cell::sync::Lazy;
+use prometheus::{IntCounter, IntGauge};
+use tauri::{AppHandle, Emitter};
+
+use crate::metrics;
+
+// 单次轮询间隔
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+// 每批最多推送的行数，避免快速增长的日志打爆事件通道
+const MAX_BATCH_LINES: usize = 500;
+
+// 活跃 tail 数量与累计读取字节数，复用全局指标注册表
+struct LogtailMetrics {
+    active: IntGauge,
+    bytes_read: IntCounter,
+}
+
+static LOGTAIL_METRICS: Lazy<LogtailMetrics> = Lazy::new(|| {
+    let active = IntGauge::new("tauri_logtail_active", "Number of active log tail subscriptions")
+        .expect("gauge");
+    let bytes_read = IntCounter::new("tauri_logtail_bytes_total", "Total bytes read by log tails")
+        .expect("counter");
+    prometheus::register(Box::new(active.clone())).expect("register gauge");
+    prometheus::register(Box::new(bytes_read.clone())).expect("register counter");
+    LogtailMetrics { active, bytes_read }
+});
+
+// 正在运行的订阅，key 为订阅 id
+static SUBSCRIPTIONS: Lazy<Mutex<HashMap<String, Arc<AtomicBool>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+// 订阅 id 生成计数器
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+// 开始尾随一个文件，返回订阅 id
+#[tauri::command]
+pub fn tail_start(app: AppHandle, path: String, from_end: bool) -> Result<String, String> {
+    metrics::observe("tail_start", move || {
+        let id = format!("tail-{}", NEXT_ID.fetch_add(1, Ordering::SeqCst));
+        let running = Arc::new(AtomicBool::new(true));
+        let event = format!("maalogs://log/{id}");
+        // 只有线程真正起来之后才登记订阅，否则一个失败的 tail_start（比如
+        // 路径不存在）会让前端永远拿不到 id，导致这条记录永久泄漏在
+        // SUBSCRIPTIONS 里，因为没人能调用 tail_stop 来清理它。
+        spawn_tail_thread(app, path, from_end, event, running.clone())?;
+        SUBSCRIPTIONS
+            .lock()
+            .expect("subscriptions lock")
+            .insert(id.clone(), running);
+        Ok(id)
+    })
+}
+
+// 停止尾随，幂等：对未知 id 不报错
+#[tauri::command]
+pub fn tail_stop(id: String) {
+    metrics::observe_infallible("tail_stop", || {
+        if let Some(running) = SUBSCRIPTIONS.lock().expect("subscriptions lock").remove(&id) {
+            running.store(false, Ordering::SeqCst);
+        }
+    })
+}
+
+fn spawn_tail_thread(
+    app: AppHandle,
+    path: String,
+    from_end: bool,
+    event: String,
+    running: Arc<AtomicBool>,
+) -> Result<(), String> {
+    let mut file = File::open(&path).map_err(|err| err.to_string())?;
+    let mut offset = if from_end {
+        file.seek(SeekFrom::End(0)).map_err(|err| err.to_string())?
+    } else {
+        0
+    };
+
+    LOGTAIL_METRICS.active.inc();
+    thread::spawn(move || {
+        let mut leftover = Vec::new();
+        while running.load(Ordering::SeqCst) {
+            // logrotate 典型地将旧文件改名后在同一路径新建文件，持有的句柄仍
+            // 指向改名后的旧 inode，仅靠 fstat 长度变化检测不到；这里额外对
+            // 路径本身重新 stat，一旦身份（设备号+inode）变化就重新打开。
+            if let Ok(path_meta) = std::fs::metadata(&path) {
+                if let Ok(handle_meta) = file.metadata() {
+                    if file_identity(&path_meta) != file_identity(&handle_meta) {
+                        match File::open(&path) {
+                            Ok(reopened) => {
+                                file = reopened;
+                                offset = 0;
+                                leftover.clear();
+                            }
+                            Err(_) => {
+                                thread::sleep(POLL_INTERVAL);
+                                continue;
+                            }
+                        }
+                    }
+                }
+            }
+
+            match file.metadata().map(|meta| meta.len()) {
+                Ok(len) if len < offset => {
+                    // 文件在原地被截断（copytruncate），从头重新开始读取
+                    offset = 0;
+                    leftover.clear();
+                    let _ = file.seek(SeekFrom::Start(0));
+                }
+                Ok(len) if len == offset => {
+                    thread::sleep(POLL_INTERVAL);
+                    continue;
+                }
+                Ok(_) => {}
+                Err(_) => {
+                    thread::sleep(POLL_INTERVAL);
+                    continue;
+                }
+            }
+
+            let mut chunk = Vec::new();
+            if file.read_to_end(&mut chunk).is_err() {
+                thread::sleep(POLL_INTERVAL);
+                continue;
+            }
+            offset += chunk.len() as u64;
+            LOGTAIL_METRICS.bytes_read.inc_by(chunk.len() as u64);
+            leftover.extend_from_slice(&chunk);
+
+            let mut lines = Vec::new();
+            let mut reader = BufReader::new(leftover.as_slice());
+            let mut consumed = 0usize;
+            loop {
+                let mut line = String::new();
+                match reader.read_line(&mut line) {
+                    Ok(0) => break,
+                    Ok(n) if line.ends_with('\n') => {
+                        consumed += n;
+                        lines.push(line.trim_end_matches(['\r', '\n']).to_string());
+                    }
+                    _ => break,
+                }
+            }
+            leftover.drain(..consumed);
+
+            for batch in lines.chunks(MAX_BATCH_LINES) {
+                let _ = app.emit(&event, batch);
+            }
+
+            thread::sleep(POLL_INTERVAL);
+        }
+
+        LOGTAIL_METRICS.active.dec();
+    });
+
+    Ok(())
+}
+
+// 文件身份标识（设备号 + inode，Windows 下为卷序列号 + 文件索引），用于判断
+// 路径上的文件是否已被替换为一个不同的文件（而不仅仅是长度变化）
+#[cfg(unix)]
+fn file_identity(meta: &std::fs::Metadata) -> (u64, u64) {
+    use std::os::unix::fs::MetadataExt;
+    (meta.dev(), meta.ino())
+}
+
+#[cfg(windows)]
+fn file_identity(meta: &std::fs::Metadata) -> (u64, u64) {
+    use std::os::windows::fs::MetadataExt;
+    (
+        meta.volume_serial_number().unwrap_or(0) as u64,
+        meta.file_index().unwrap_or(0),
+    )
+}
+
+#[cfg(not(any(unix, windows)))]
+fn file_identity(_meta: &std::fs::Metadata) -> (u64, u64) {
+    (0, 0)
+}
@@ -2,11 +2,16 @@
 // 本模块提供 Prometheus 指标收集功能
 
 use once_cell::sync::Lazy;
+use prometheus::core::{Collector, Desc};
+use prometheus::proto::MetricFamily;
 use prometheus::{
-    Encoder, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, Opts, TextEncoder,
+    Counter, Encoder, Gauge, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, Opts, TextEncoder,
 };
 use std::net::SocketAddr;
+use std::sync::Mutex;
 use std::thread;
+use std::time::{Duration, Instant};
+use sysinfo::{Pid, ProcessRefreshKind, RefreshKind, System};
 use tiny_http::{Header, Response, Server};
 
 // 全局指标单例
@@ -19,6 +24,8 @@ pub struct Metrics {
     #[allow(dead_code)]
     command_duration: HistogramVec,
     #[allow(dead_code)]
+    command_in_flight: IntGauge,
+    #[allow(dead_code)]
     app_up: IntGauge,
 }
 
@@ -35,14 +42,23 @@ impl Metrics {
             &["command"][..],
         )
         .expect("histogram");
+        let command_in_flight =
+            IntGauge::new("tauri_command_in_flight", "Tauri commands currently executing")
+                .expect("gauge");
         let app_up = IntGauge::new("tauri_app_up", "Tauri app up").expect("gauge");
         prometheus::register(Box::new(command_total.clone())).expect("register counter");
         prometheus::register(Box::new(command_duration.clone())).expect("register histogram");
+        prometheus::register(Box::new(command_in_flight.clone())).expect("register gauge");
         prometheus::register(Box::new(app_up.clone())).expect("register gauge");
+        // 暴露标准的 process_* 指标（内存、CPU、启动时间、打开的文件描述符）。
+        // prometheus 自带的 ProcessCollector 只支持 Linux，而本应用同时发布
+        // Windows/macOS 构建，因此改用跨平台的 sysinfo 自行采样。
+        prometheus::register(Box::new(ProcessMetrics::for_self())).expect("register process collector");
         app_up.set(1);
         Self {
             command_total,
             command_duration,
+            command_in_flight,
             app_up,
         }
     }
@@ -60,21 +76,70 @@ pub fn observe_command(command: &str, status: &str, duration_seconds: f64) {
         .observe(duration_seconds);
 }
 
+// 执行一个 Tauri 命令并自动记录成功/失败状态、耗时与并发数
+//
+// 捕获闭包 panic 视为 "error"，避免一个失败的命令完全不留痕迹。
+pub fn observe<T, E>(command: &str, f: impl FnOnce() -> Result<T, E> + std::panic::UnwindSafe) -> Result<T, E> {
+    METRICS.command_in_flight.inc();
+    let start = std::time::Instant::now();
+    let outcome = std::panic::catch_unwind(f);
+    let duration = start.elapsed().as_secs_f64();
+    METRICS.command_in_flight.dec();
+
+    let status = match &outcome {
+        Ok(Ok(_)) => "success",
+        Ok(Err(_)) => "error",
+        Err(_) => "error",
+    };
+    observe_command(command, status, duration);
+
+    match outcome {
+        Ok(result) => result,
+        Err(payload) => std::panic::resume_unwind(payload),
+    }
+}
+
+// 为不返回 Result 的命令提供的便捷包装，状态恒为 "success"（除非 panic）
+pub fn observe_infallible<T>(command: &str, f: impl FnOnce() -> T + std::panic::UnwindSafe) -> T {
+    match observe(command, || Ok::<T, std::convert::Infallible>(f())) {
+        Ok(value) => value,
+        Err(never) => match never {},
+    }
+}
+
 // 启动指标 HTTP 服务器
-pub fn start_metrics_server(port: u16) {
-    let address = SocketAddr::from(([127, 0, 0, 1], port));
+//
+// bind 允许绑定到非回环地址以支持容器化/远程抓取场景；一旦设置了 token，
+// /metrics 请求需要携带匹配的 `Authorization: Bearer <token>`，否则返回 401。
+// /healthz 始终返回 200 ok，不做鉴权；其他路径一律返回 404。
+pub fn start_metrics_server(bind: String, port: u16, token: Option<String>) {
     thread::spawn(move || {
+        let address: SocketAddr = match format!("{bind}:{port}").parse() {
+            Ok(address) => address,
+            Err(_) => return,
+        };
         let server = match Server::http(address) {
             Ok(server) => server,
             Err(_) => return,
         };
         let encoder = TextEncoder::new();
         for request in server.incoming_requests() {
+            if request.url() == "/healthz" {
+                let _ = request.respond(Response::from_string("ok"));
+                continue;
+            }
             if request.url() != "/metrics" {
                 let response = Response::from_string("not found").with_status_code(404);
                 let _ = request.respond(response);
                 continue;
             }
+            if let Some(expected) = &token {
+                if !authorized(&request, expected) {
+                    let response = Response::from_string("unauthorized").with_status_code(401);
+                    let _ = request.respond(response);
+                    continue;
+                }
+            }
             let metric_families = prometheus::gather();
             let mut buffer = Vec::new();
             if encoder.encode(&metric_families, &mut buffer).is_err() {
@@ -90,3 +155,184 @@ pub fn start_metrics_server(port: u16) {
         }
     });
 }
+
+// 校验请求的 `Authorization: Bearer <token>` 头是否匹配期望的 token
+fn authorized(request: &tiny_http::Request, expected: &str) -> bool {
+    let prefix = "Bearer ";
+    request
+        .headers()
+        .iter()
+        .find(|header| header.field.as_str().as_str().eq_ignore_ascii_case("Authorization"))
+        .map(|header| header.value.as_str())
+        .and_then(|value| value.strip_prefix(prefix))
+        .is_some_and(|presented| constant_time_eq(presented.as_bytes(), expected.as_bytes()))
+}
+
+// 恒定时间比较两个字节串，避免通过响应耗时泄露 token 内容
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut diff = 0u8;
+    for (x, y) in a.iter().zip(b.iter()) {
+        diff |= x ^ y;
+    }
+    diff == 0
+}
+
+// 启动 Pushgateway 推送线程
+//
+// 桌面应用常运行在 NAT 之后，无法被 Prometheus 拉取，这里改为主动周期性
+// 推送到一个已有的 Pushgateway 实例。
+pub fn start_pushgateway(url: String, interval_ms: u64) {
+    thread::spawn(move || {
+        let encoder = TextEncoder::new();
+        let instance = hostname();
+        // 将版本纳入分组键路径段，而非自定义 HTTP 头：Pushgateway 只按 URL
+        // 路径分组，不同版本的推送需要落在不同的分组键上，否则会互相覆盖。
+        let endpoint = format!(
+            "{}/metrics/job/maalogs/instance/{}/version/{}",
+            url.trim_end_matches('/'),
+            instance,
+            env!("CARGO_PKG_VERSION"),
+        );
+        loop {
+            let metric_families = prometheus::gather();
+            let mut buffer = Vec::new();
+            if encoder.encode(&metric_families, &mut buffer).is_ok() {
+                let agent = ureq::agent();
+                let _ = agent
+                    .post(&endpoint)
+                    .set("Content-Type", "text/plain; version=0.0.4; charset=utf-8")
+                    .send_bytes(&buffer);
+            }
+            thread::sleep(Duration::from_millis(interval_ms));
+        }
+    });
+}
+
+// 获取本机主机名，用于 Pushgateway 的 instance 分组标签
+fn hostname() -> String {
+    std::env::var("HOSTNAME")
+        .or_else(|_| std::env::var("COMPUTERNAME"))
+        .unwrap_or_else(|_| "unknown-host".to_string())
+}
+
+// 跨平台的 process_* 指标采集器，基于 sysinfo 在每次抓取时重新采样
+//
+// 文件描述符数量不经过 sysinfo，只在 Linux 上通过读取 /proc/self/fd 获得；
+// 其余平台上该指标固定为 0。
+struct ProcessMetrics {
+    pid: Pid,
+    system: Mutex<System>,
+    last_sample: Mutex<Instant>,
+    resident_memory: Gauge,
+    virtual_memory: Gauge,
+    cpu_seconds_total: Counter,
+    start_time: Gauge,
+    open_fds: IntGauge,
+}
+
+impl ProcessMetrics {
+    fn for_self() -> Self {
+        let pid = Pid::from_u32(std::process::id());
+        let system = System::new_with_specifics(
+            RefreshKind::nothing().with_processes(ProcessRefreshKind::everything()),
+        );
+        let resident_memory = Gauge::new(
+            "process_resident_memory_bytes",
+            "Resident memory size in bytes",
+        )
+        .expect("gauge");
+        let virtual_memory = Gauge::new(
+            "process_virtual_memory_bytes",
+            "Virtual memory size in bytes",
+        )
+        .expect("gauge");
+        let cpu_seconds_total = Counter::new(
+            "process_cpu_seconds_total",
+            "Total user and system CPU time spent in seconds",
+        )
+        .expect("counter");
+        let start_time = Gauge::new(
+            "process_start_time_seconds",
+            "Start time of the process since unix epoch in seconds",
+        )
+        .expect("gauge");
+        let open_fds = IntGauge::new("process_open_fds", "Number of open file descriptors")
+            .expect("gauge");
+
+        Self {
+            pid,
+            system: Mutex::new(system),
+            last_sample: Mutex::new(Instant::now()),
+            resident_memory,
+            virtual_memory,
+            cpu_seconds_total,
+            start_time,
+            open_fds,
+        }
+    }
+
+    // 打开的文件描述符数量；仅 Linux 支持，其余平台返回 0
+    #[cfg(target_os = "linux")]
+    fn count_open_fds() -> i64 {
+        std::fs::read_dir("/proc/self/fd")
+            .map(|entries| entries.count() as i64)
+            .unwrap_or(0)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn count_open_fds() -> i64 {
+        0
+    }
+}
+
+impl Collector for ProcessMetrics {
+    fn desc(&self) -> Vec<&Desc> {
+        self.resident_memory
+            .desc()
+            .into_iter()
+            .chain(self.virtual_memory.desc())
+            .chain(self.cpu_seconds_total.desc())
+            .chain(self.start_time.desc())
+            .chain(self.open_fds.desc())
+            .collect()
+    }
+
+    fn collect(&self) -> Vec<MetricFamily> {
+        let mut system = self.system.lock().expect("process metrics lock");
+        system.refresh_processes_specifics(
+            sysinfo::ProcessesToUpdate::Some(&[self.pid]),
+            true,
+            ProcessRefreshKind::everything(),
+        );
+
+        if let Some(process) = system.process(self.pid) {
+            self.resident_memory.set(process.memory() as f64);
+            self.virtual_memory.set(process.virtual_memory() as f64);
+            self.start_time.set(process.start_time() as f64);
+
+            // sysinfo 只暴露一个即时 CPU 占用百分比，这里用两次采样之间的
+            // 墙钟耗时对其积分，近似累加到 process_cpu_seconds_total 计数器上。
+            // 和标准的进程采集器一样用 counter 而非 gauge 暴露，这样现有面板
+            // 里对它做 rate()/increase() 才有意义。
+            let mut last_sample = self.last_sample.lock().expect("cpu sample lock");
+            let now = Instant::now();
+            let elapsed = now.duration_since(*last_sample).as_secs_f64();
+            let cpu_delta = (process.cpu_usage() as f64 / 100.0) * elapsed;
+            self.cpu_seconds_total.inc_by(cpu_delta.max(0.0));
+            *last_sample = now;
+        }
+        self.open_fds.set(Self::count_open_fds());
+
+        self.resident_memory
+            .collect()
+            .into_iter()
+            .chain(self.virtual_memory.collect())
+            .chain(self.cpu_seconds_total.collect())
+            .chain(self.start_time.collect())
+            .chain(self.open_fds.collect())
+            .collect()
+    }
+}
@@ -0,0 +1,106 @@
+// 指标推送模块
+// 本模块定期将已注册的 Prometheus 指标打包为 JSON，并通过 Tauri 事件
+// 推送给前端，使日志查看器无需轮询 /metrics 即可展示实时指标。
+
+use std::thread;
+use std::time::Duration;
+
+use prometheus::proto::MetricFamily;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+// 推送给前端的事件名称
+const METRICS_EVENT: &str = "maalogs://metrics";
+
+// 单个直方图分桶的精简表示
+#[derive(Serialize)]
+struct HistogramBucket {
+    upper_bound: f64,
+    cumulative_count: u64,
+}
+
+// 单个指标样本的精简表示
+//
+// value 对 counter/gauge 是其原始值；对 histogram 是 sample_sum（总耗时），
+// 需要配合 sample_count 才能在前端算出平均延迟，配合 buckets 才能算分位数。
+#[derive(Serialize)]
+struct MetricSample {
+    labels: Vec<(String, String)>,
+    value: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sample_count: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    buckets: Option<Vec<HistogramBucket>>,
+}
+
+// 一组同名指标的精简表示
+#[derive(Serialize)]
+struct MetricSnapshot {
+    name: String,
+    help: String,
+    samples: Vec<MetricSample>,
+}
+
+// 将一个 MetricFamily 转换为精简快照，适配 counter/gauge/histogram
+fn to_snapshot(family: &MetricFamily) -> MetricSnapshot {
+    let samples = family
+        .get_metric()
+        .iter()
+        .map(|metric| {
+            let labels = metric
+                .get_label()
+                .iter()
+                .map(|pair| (pair.get_name().to_string(), pair.get_value().to_string()))
+                .collect();
+
+            let (value, sample_count, buckets) = if metric.has_counter() {
+                (metric.get_counter().get_value(), None, None)
+            } else if metric.has_gauge() {
+                (metric.get_gauge().get_value(), None, None)
+            } else if metric.has_histogram() {
+                let histogram = metric.get_histogram();
+                let buckets = histogram
+                    .get_bucket()
+                    .iter()
+                    .map(|bucket| HistogramBucket {
+                        upper_bound: bucket.get_upper_bound(),
+                        cumulative_count: bucket.get_cumulative_count(),
+                    })
+                    .collect();
+                (
+                    histogram.get_sample_sum(),
+                    Some(histogram.get_sample_count()),
+                    Some(buckets),
+                )
+            } else {
+                (0.0, None, None)
+            };
+
+            MetricSample {
+                labels,
+                value,
+                sample_count,
+                buckets,
+            }
+        })
+        .collect();
+    MetricSnapshot {
+        name: family.get_name().to_string(),
+        help: family.get_help().to_string(),
+        samples,
+    }
+}
+
+// 启动后台线程，按配置的间隔将指标快照推送给前端
+//
+// interval_ms 为 0 时不启动推送线程。
+pub fn start(app: AppHandle, interval_ms: u64) {
+    if interval_ms == 0 {
+        return;
+    }
+    thread::spawn(move || loop {
+        let snapshot: Vec<MetricSnapshot> = prometheus::gather().iter().map(to_snapshot).collect();
+        let _ = app.emit(METRICS_EVENT, &snapshot);
+        thread::sleep(Duration::from_millis(interval_ms));
+    });
+}